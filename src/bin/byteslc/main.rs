@@ -0,0 +1,47 @@
+use std::error::Error;
+use std::io::{self, BufWriter};
+use std::process;
+
+use clap::Clap;
+
+use slices::filter::{Filter, FilterSet};
+use slices::reader::open_reader;
+use slices::slicer::{ByteSlicer, Slicer};
+
+#[derive(Clap)]
+#[clap(
+    name = "byteslc",
+    version = "1.0.0",
+    author = "Jace Tan <jaceys.tan@gmail.com>"
+)]
+struct Opts {
+    /// Path to input file
+    filepath: Option<String>,
+
+    #[clap(short, long)]
+    /// Filters to be applied
+    filters: Vec<Filter>,
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let opts: Opts = Opts::parse();
+
+    let reader =
+        open_reader(opts.filepath.as_deref()).map_err(|err| format!("failed to open input: {}", err))?;
+    let mut writer = BufWriter::new(io::stdout());
+
+    let mut slicer = ByteSlicer::new(reader, FilterSet::new(opts.filters));
+
+    slicer
+        .slice(&mut writer)
+        .map_err(|err| format!("slice operation failed: {}", err))?;
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use std::error::Error;
+use std::io::{self, BufWriter};
+use std::process;
+
+use clap::Clap;
+
+use slices::filter::{Filter, FilterSet};
+use slices::reader::open_reader;
+use slices::slicer::{ByteSlicer, ColSlicer, RowSlicer, Slicer};
+
+#[derive(Clap)]
+#[clap(
+    name = "slice",
+    version = "1.0.0",
+    author = "Jace Tan <jaceys.tan@gmail.com>"
+)]
+struct Opts {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Clap)]
+enum Command {
+    /// Slice rows (lines) out of the input
+    Row(RowOpts),
+    /// Slice columns (fields) out of the input
+    Col(ColOpts),
+    /// Slice a raw byte range out of the input
+    Byte(ByteOpts),
+}
+
+#[derive(Clap)]
+struct RowOpts {
+    /// Path to input file
+    filepath: Option<String>,
+
+    #[clap(short, long)]
+    /// Filters to be applied
+    filters: Vec<Filter>,
+
+    #[clap(short = "v", long)]
+    /// Invert the filters, selecting everything that does NOT match
+    invert: bool,
+}
+
+#[derive(Clap)]
+struct ColOpts {
+    /// Path to input file
+    filepath: Option<String>,
+
+    #[clap(short, long)]
+    /// Filters to be applied
+    filters: Vec<Filter>,
+
+    #[clap(short, long)]
+    /// Input field delimiter, defaults to collapsing consecutive whitespace
+    delimiter: Option<String>,
+
+    #[clap(short, long = "output-delimiter")]
+    /// Output field delimiter, defaults to the input delimiter
+    output_delimiter: Option<String>,
+
+    #[clap(short = "v", long)]
+    /// Invert the filters, selecting every column that does NOT match
+    invert: bool,
+}
+
+#[derive(Clap)]
+struct ByteOpts {
+    /// Path to input file
+    filepath: Option<String>,
+
+    #[clap(short, long)]
+    /// Filters to be applied
+    filters: Vec<Filter>,
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let opts: Opts = Opts::parse();
+    let mut writer = BufWriter::new(io::stdout());
+
+    match opts.command {
+        Command::Row(opts) => {
+            let reader = open_reader(opts.filepath.as_deref())
+                .map_err(|err| format!("failed to open input: {}", err))?;
+            let mut slicer = RowSlicer::new(
+                reader,
+                FilterSet::new(opts.filters).invert(opts.invert),
+            );
+            slicer
+                .slice(&mut writer)
+                .map_err(|err| format!("slice operation failed: {}", err))?;
+        }
+        Command::Col(opts) => {
+            let reader = open_reader(opts.filepath.as_deref())
+                .map_err(|err| format!("failed to open input: {}", err))?;
+            let mut slicer = ColSlicer::new(
+                reader,
+                FilterSet::new(opts.filters).invert(opts.invert),
+                opts.delimiter,
+                opts.output_delimiter,
+            );
+            slicer
+                .slice(&mut writer)
+                .map_err(|err| format!("slice operation failed: {}", err))?;
+        }
+        Command::Byte(opts) => {
+            let reader = open_reader(opts.filepath.as_deref())
+                .map_err(|err| format!("failed to open input: {}", err))?;
+            let mut slicer = ByteSlicer::new(reader, FilterSet::new(opts.filters));
+            slicer
+                .slice(&mut writer)
+                .map_err(|err| format!("slice operation failed: {}", err))?;
+        }
+    }
+
+    Ok(())
+}
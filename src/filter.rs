@@ -0,0 +1,341 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A single filter expression parsed from a `-f`/`--filters` flag, e.g. `3`, `1:5`, `6:`, `:2`,
+/// `:` or `1:10:2`. An exact index, or either bound of a range, may also carry a leading `-` to
+/// count from the end of the stream instead of the start, e.g. `-1` for the last index or `-3:`
+/// for the last three.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    /// Matches a single index.
+    Exact(i64),
+    /// Matches an inclusive range of indices, stepping by the third field (1 when unspecified).
+    /// Either bound may be omitted to mean "from the start" or "to the end" respectively.
+    Range(Option<i64>, Option<i64>, i64),
+}
+
+#[derive(Debug)]
+pub enum ParseFilterError {
+    Invalid(String),
+    ZeroStep(String),
+}
+
+impl fmt::Display for ParseFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseFilterError::Invalid(s) => write!(f, "invalid filter `{}`", s),
+            ParseFilterError::ZeroStep(s) => write!(f, "step must not be zero in filter `{}`", s),
+        }
+    }
+}
+
+impl std::error::Error for ParseFilterError {}
+
+impl FromStr for Filter {
+    type Err = ParseFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseFilterError::Invalid(s.to_string());
+        let bound = |field: &str| -> Result<Option<i64>, ParseFilterError> {
+            if field.is_empty() {
+                Ok(None)
+            } else {
+                Some(field.parse().map_err(|_| invalid())).transpose()
+            }
+        };
+
+        match s.split(':').collect::<Vec<&str>>().as_slice() {
+            [exact] => Ok(Filter::Exact(exact.parse().map_err(|_| invalid())?)),
+            [start, end] => Ok(Filter::Range(bound(start)?, bound(end)?, 1)),
+            [start, end, step] => {
+                let step = if step.is_empty() {
+                    1
+                } else {
+                    step.parse().map_err(|_| invalid())?
+                };
+                if step == 0 {
+                    return Err(ParseFilterError::ZeroStep(s.to_string()));
+                }
+
+                Ok(Filter::Range(bound(start)?, bound(end)?, step))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl Filter {
+    /// Returns `true` if any bound of this filter is relative to the end of the stream, and so
+    /// can only be resolved once the total number of lines is known.
+    fn is_relative(&self) -> bool {
+        match self {
+            Filter::Exact(i) => *i < 0,
+            Filter::Range(start, end, _) => {
+                start.is_some_and(|i| i < 0) || end.is_some_and(|i| i < 0)
+            }
+        }
+    }
+
+    /// Rewrites any end-relative bounds into absolute, 1-based indices now that the total number
+    /// of lines `len` is known, clamping out-of-range magnitudes to the nearest valid index.
+    /// Returns `None` if an exact filter's end-relative index, or a range's end-relative end
+    /// bound, falls before the start of the stream, since there's no absolute index left for it
+    /// to match.
+    fn resolve(&self, len: i64) -> Option<Filter> {
+        let absolute = |i: i64| -> i64 {
+            if i < 0 {
+                (len + 1 + i).clamp(1, len.max(1))
+            } else {
+                i
+            }
+        };
+
+        match self {
+            Filter::Exact(i) if *i < 0 && len + 1 + i < 1 => None,
+            Filter::Exact(i) => Some(Filter::Exact(absolute(*i))),
+            // An end bound that resolves below the first line can't match anything, mirroring
+            // how an out-of-range `Exact` is dropped above. Unlike the start bound, which clamps
+            // up to line 1 so an overly-negative `-N:` still means "the whole stream", an
+            // overly-negative `:-N` means there's nothing left before the end, so don't clamp it.
+            Filter::Range(_, Some(end), _) if *end < 0 && len + 1 + end < 1 => None,
+            Filter::Range(start, end, step) => {
+                Some(Filter::Range(start.map(absolute), end.map(absolute), *step))
+            }
+        }
+    }
+
+    fn apply(&self, index: u64) -> bool {
+        let index = index as i64;
+
+        match self {
+            Filter::Exact(i) => index == *i,
+            Filter::Range(start, end, step) => {
+                let in_bounds =
+                    start.is_none_or(|s| index >= s) && end.is_none_or(|e| index <= e);
+                in_bounds && (index - start.unwrap_or(1)).rem_euclid(*step) == 0
+            }
+        }
+    }
+}
+
+pub struct FilterSet {
+    filters: Vec<Filter>,
+    invert: bool,
+    // Whether the set was constructed with any filters at all, tracked separately from
+    // `filters` so that `resolve` dropping every filter (an out-of-range exact end-relative
+    // index) doesn't get confused with the user never having passed `-f` in the first place.
+    has_filters: bool,
+}
+
+impl FilterSet {
+    pub fn new(filters: Vec<Filter>) -> Self {
+        let has_filters = !filters.is_empty();
+        FilterSet {
+            filters,
+            invert: false,
+            has_filters,
+        }
+    }
+
+    /// Inverts the result of `apply`, so that the set matches everything it previously didn't.
+    pub fn invert(mut self, invert: bool) -> Self {
+        self.invert = invert;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.has_filters
+    }
+
+    pub fn is_inverted(&self) -> bool {
+        self.invert
+    }
+
+    /// Returns `true` if resolving this set requires knowing the total number of lines, i.e. at
+    /// least one filter has a bound relative to the end of the stream.
+    pub fn is_relative(&self) -> bool {
+        self.filters.iter().any(Filter::is_relative)
+    }
+
+    /// Rewrites any end-relative filters into absolute indices now that the total number of
+    /// lines `len` is known.
+    pub fn resolve(&self, len: usize) -> FilterSet {
+        FilterSet {
+            filters: self
+                .filters
+                .iter()
+                .filter_map(|f| f.resolve(len as i64))
+                .collect(),
+            invert: self.invert,
+            has_filters: self.has_filters,
+        }
+    }
+
+    pub fn apply(&self, index: u64) -> bool {
+        let matched = !self.has_filters || self.filters.iter().any(|f| f.apply(index));
+        matched != self.invert
+    }
+
+    /// Returns `Some(n)` only when this set is expressible purely as "the last `n` lines",
+    /// i.e. a single end-relative range with no end bound (`-n:`). This lets callers stream
+    /// through a fixed-size window instead of buffering the whole input to resolve bounds.
+    /// Inverted sets never qualify, since "everything but the last `n` lines" isn't a bounded
+    /// window.
+    pub fn suffix_window(&self) -> Option<u32> {
+        if self.invert {
+            return None;
+        }
+
+        match self.filters.as_slice() {
+            [Filter::Range(Some(start), None, 1)] if *start < 0 => {
+                start.checked_neg().and_then(|n| u32::try_from(n).ok())
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn filter_from_str_exact_ok() {
+        assert_eq!(Filter::from_str("3").unwrap(), Filter::Exact(3));
+    }
+
+    #[test]
+    fn filter_from_str_exact_negative_ok() {
+        assert_eq!(Filter::from_str("-1").unwrap(), Filter::Exact(-1));
+    }
+
+    #[test]
+    fn filter_from_str_range_ok() {
+        assert_eq!(Filter::from_str("1:5").unwrap(), Filter::Range(Some(1), Some(5), 1));
+    }
+
+    #[test]
+    fn filter_from_str_range_negative_ok() {
+        assert_eq!(Filter::from_str("-3:").unwrap(), Filter::Range(Some(-3), None, 1));
+        assert_eq!(Filter::from_str(":-2").unwrap(), Filter::Range(None, Some(-2), 1));
+    }
+
+    #[test]
+    fn filter_from_str_range_step_ok() {
+        assert_eq!(Filter::from_str("1:10:2").unwrap(), Filter::Range(Some(1), Some(10), 2));
+        assert_eq!(Filter::from_str("3::2").unwrap(), Filter::Range(Some(3), None, 2));
+    }
+
+    #[test]
+    fn filter_from_str_range_step_zero_err() {
+        let err = Filter::from_str("1:10:0").unwrap_err();
+        assert_eq!(err.to_string(), "step must not be zero in filter `1:10:0`");
+    }
+
+    #[test]
+    fn filter_from_str_invalid_err() {
+        assert!(Filter::from_str("abc").is_err());
+        assert!(Filter::from_str("1:2:3:4").is_err());
+    }
+
+    #[test]
+    fn filter_set_resolve_clamps_out_of_range_ok() {
+        let set = FilterSet::new(vec![Filter::from_str("-10:").unwrap()]);
+        let resolved = set.resolve(3);
+        assert!(resolved.apply(1));
+    }
+
+    #[test]
+    fn filter_set_suffix_window_ok() {
+        let set = FilterSet::new(vec![Filter::from_str("-3:").unwrap()]);
+        assert_eq!(set.suffix_window(), Some(3));
+    }
+
+    #[test]
+    fn filter_set_suffix_window_none_ok() {
+        assert_eq!(
+            FilterSet::new(vec![Filter::from_str("-3:").unwrap(), Filter::from_str("1").unwrap()])
+                .suffix_window(),
+            None
+        );
+        assert_eq!(FilterSet::new(vec![Filter::from_str(":-3").unwrap()]).suffix_window(), None);
+        assert_eq!(FilterSet::new(vec![Filter::from_str("1:").unwrap()]).suffix_window(), None);
+    }
+
+    #[test]
+    fn filter_set_apply_step_ok() {
+        let set = FilterSet::new(vec![Filter::from_str("1:10:2").unwrap()]);
+        assert!(set.apply(1));
+        assert!(!set.apply(2));
+        assert!(set.apply(3));
+        assert!(!set.apply(10));
+    }
+
+    #[test]
+    fn filter_set_apply_step_open_ended_ok() {
+        let set = FilterSet::new(vec![Filter::from_str("3::2").unwrap()]);
+        assert!(!set.apply(2));
+        assert!(set.apply(3));
+        assert!(!set.apply(4));
+        assert!(set.apply(5));
+    }
+
+    #[test]
+    fn filter_set_apply_step_larger_than_width_ok() {
+        let set = FilterSet::new(vec![Filter::from_str("1:3:5").unwrap()]);
+        assert!(set.apply(1));
+        assert!(!set.apply(2));
+        assert!(!set.apply(3));
+    }
+
+    #[test]
+    fn filter_set_apply_inverted_ok() {
+        let set = FilterSet::new(vec![Filter::from_str("2:4").unwrap()]).invert(true);
+        assert!(set.apply(1));
+        assert!(!set.apply(2));
+        assert!(!set.apply(3));
+        assert!(!set.apply(4));
+        assert!(set.apply(5));
+    }
+
+    #[test]
+    fn filter_set_apply_inverted_empty_ok() {
+        let set = FilterSet::new(vec![]).invert(true);
+        assert!(!set.apply(1));
+        assert!(!set.apply(2));
+    }
+
+    #[test]
+    fn filter_set_suffix_window_inverted_none_ok() {
+        let set = FilterSet::new(vec![Filter::from_str("-3:").unwrap()]).invert(true);
+        assert_eq!(set.suffix_window(), None);
+    }
+
+    #[test]
+    fn filter_set_apply_offset_beyond_u32_ok() {
+        // `ByteSlicer` streams offsets as `u64` for streams larger than 4 GiB; `apply` must not
+        // truncate them back down to `u32` and wrap.
+        let set = FilterSet::new(vec![Filter::from_str("1").unwrap()]);
+        let beyond_u32 = u32::MAX as u64 + 2;
+        assert!(!set.apply(beyond_u32));
+        assert!(set.apply(1));
+    }
+
+    #[test]
+    fn filter_set_resolve_drops_exact_out_of_range_ok() {
+        let set = FilterSet::new(vec![Filter::from_str("-10").unwrap()]);
+        let resolved = set.resolve(3);
+        assert!(!resolved.apply(1));
+        assert!(!resolved.apply(2));
+        assert!(!resolved.apply(3));
+    }
+
+    #[test]
+    fn filter_set_resolve_drops_range_end_out_of_range_ok() {
+        let set = FilterSet::new(vec![Filter::from_str(":-10").unwrap()]);
+        let resolved = set.resolve(3);
+        assert!(!resolved.apply(1));
+        assert!(!resolved.apply(2));
+        assert!(!resolved.apply(3));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod filter;
+pub mod reader;
+pub mod slicer;
@@ -0,0 +1,10 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+
+/// Opens `path` for buffered reading, falling back to stdin when `path` is `None` or `-`.
+pub fn open_reader(path: Option<&str>) -> io::Result<Box<dyn BufRead>> {
+    match path {
+        Some("-") | None => Ok(Box::new(BufReader::new(io::stdin()))),
+        Some(input) => Ok(Box::new(BufReader::new(File::open(input)?))),
+    }
+}
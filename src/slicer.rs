@@ -0,0 +1,886 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::io::{BufRead, Read, Write};
+
+use crate::filter::FilterSet;
+
+// Size of the fixed buffer `ByteSlicer` reads through; large enough to amortize syscalls without
+// holding more than a page or two of the stream in memory at once.
+const BYTE_CHUNK_SIZE: usize = 8192;
+
+/// Slices lines, fields within lines, or bytes out of a stream according to a `FilterSet`.
+pub trait Slicer {
+    fn slice<W: Write>(&mut self, writer: &mut W) -> Result<(), Box<dyn Error>>;
+}
+
+pub struct RowSlicer<R: BufRead> {
+    reader: R,
+    filters: FilterSet,
+}
+
+impl<R: BufRead> RowSlicer<R> {
+    pub fn new(reader: R, filters: FilterSet) -> Self {
+        RowSlicer { reader, filters }
+    }
+
+    // Filters with an end-relative bound can only be resolved once the total number of lines is
+    // known, so we buffer the whole stream before emitting anything.
+    fn slice_buffered<W: Write>(&mut self, writer: &mut W) -> Result<(), Box<dyn Error>> {
+        let mut lines = Vec::new();
+        let mut buf = String::new();
+
+        loop {
+            match self.reader.read_line(&mut buf) {
+                Ok(0) => break,
+                Ok(_) => {
+                    lines.push(buf.clone());
+                    buf.clear();
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let filters = self.filters.resolve(lines.len());
+        for (index, line) in lines.iter().enumerate() {
+            if filters.apply(1 + index as u64) {
+                write!(writer, "{}", line)?;
+            }
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    // A filter set that reduces to "last N lines" doesn't need to know the total line count to
+    // resolve its bounds, so we can stream through with a fixed-size ring buffer instead of
+    // buffering the whole input.
+    fn slice_suffix<W: Write>(&mut self, writer: &mut W, width: u32) -> Result<(), Box<dyn Error>> {
+        // Don't pre-reserve `width` entries: `width` is user-supplied and can vastly exceed the
+        // actual number of lines in the stream (or even overflow an allocation), so let the deque
+        // grow with what we've actually read instead of the requested window size.
+        let mut window: VecDeque<String> = VecDeque::new();
+        let mut buf = String::new();
+
+        loop {
+            match self.reader.read_line(&mut buf) {
+                Ok(0) => break,
+                Ok(_) => {
+                    window.push_back(buf.clone());
+                    if window.len() > width as usize {
+                        window.pop_front();
+                    }
+
+                    buf.clear();
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        for line in &window {
+            write!(writer, "{}", line)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Slicer for RowSlicer<R> {
+    fn slice<W: Write>(&mut self, writer: &mut W) -> Result<(), Box<dyn Error>> {
+        if let Some(width) = self.filters.suffix_window() {
+            return self.slice_suffix(writer, width);
+        }
+
+        if self.filters.is_relative() {
+            return self.slice_buffered(writer);
+        }
+
+        let mut buf = String::new();
+        let mut index = 0;
+
+        loop {
+            match self.reader.read_line(&mut buf) {
+                Ok(0) => break,
+                Ok(_) => {
+                    if self.filters.apply(1 + index as u64) {
+                        write!(writer, "{}", buf)?;
+                    }
+
+                    buf.clear();
+                    index += 1;
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+pub struct ColSlicer<R: BufRead> {
+    reader: R,
+    filters: FilterSet,
+    delimiter: Option<String>,
+    output_delimiter: Option<String>,
+}
+
+impl<R: BufRead> ColSlicer<R> {
+    pub fn new(
+        reader: R,
+        filters: FilterSet,
+        delimiter: Option<String>,
+        output_delimiter: Option<String>,
+    ) -> Self {
+        ColSlicer {
+            reader,
+            filters,
+            delimiter,
+            output_delimiter,
+        }
+    }
+}
+
+impl<R: BufRead> Slicer for ColSlicer<R> {
+    fn slice<W: Write>(&mut self, writer: &mut W) -> Result<(), Box<dyn Error>> {
+        let mut buf = String::new();
+
+        loop {
+            match self.reader.read_line(&mut buf) {
+                Ok(0) => break,
+                Ok(_) => {
+                    if self.filters.is_empty() && self.filters.is_inverted() {
+                        // Every column is excluded when inverting an empty filter set, so there's
+                        // nothing left to print.
+                    } else if self.filters.is_empty() {
+                        write!(writer, "{}", buf)?;
+                    } else {
+                        let line = buf.trim_end_matches('\n');
+                        let fields: Vec<&str> = match &self.delimiter {
+                            Some(delimiter) => line.split(delimiter.as_str()).collect(),
+                            None => line.split_whitespace().collect(),
+                        };
+                        let output_delimiter = self
+                            .output_delimiter
+                            .as_deref()
+                            .or(self.delimiter.as_deref())
+                            .unwrap_or(" ");
+
+                        // Negative (end-relative) bounds are only resolvable once the number of
+                        // fields on this line is known, unlike `RowSlicer`'s line count, which is
+                        // known once for the whole stream. Resolve per line instead.
+                        let resolved = self
+                            .filters
+                            .is_relative()
+                            .then(|| self.filters.resolve(fields.len()));
+                        let filters = resolved.as_ref().unwrap_or(&self.filters);
+
+                        let columns: Vec<&str> = fields
+                            .into_iter()
+                            .enumerate()
+                            .filter(|&(index, _)| filters.apply(1 + index as u64))
+                            .map(|(_, col)| col)
+                            .collect();
+
+                        writeln!(writer, "{}", columns.join(output_delimiter))?;
+                    }
+
+                    buf.clear();
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+pub struct ByteSlicer<R: Read> {
+    reader: R,
+    filters: FilterSet,
+}
+
+impl<R: Read> ByteSlicer<R> {
+    pub fn new(reader: R, filters: FilterSet) -> Self {
+        ByteSlicer { reader, filters }
+    }
+
+    // Byte offsets with an end-relative bound can only be resolved once the total stream length
+    // is known, so we buffer the whole stream before emitting anything, same as `RowSlicer`.
+    fn slice_buffered<W: Write>(&mut self, writer: &mut W) -> Result<(), Box<dyn Error>> {
+        let mut bytes = Vec::new();
+        self.reader.read_to_end(&mut bytes)?;
+
+        let filters = self.filters.resolve(bytes.len());
+        for (index, &byte) in bytes.iter().enumerate() {
+            if filters.apply(1 + index as u64) {
+                writer.write_all(&[byte])?;
+            }
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<R: Read> Slicer for ByteSlicer<R> {
+    fn slice<W: Write>(&mut self, writer: &mut W) -> Result<(), Box<dyn Error>> {
+        if self.filters.is_relative() {
+            return self.slice_buffered(writer);
+        }
+
+        let mut buf = [0u8; BYTE_CHUNK_SIZE];
+        let mut index: u64 = 0;
+
+        loop {
+            match self.reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    // Coalesce each contiguous run of matched bytes within the chunk into a
+                    // single `write_all` instead of one syscall-absorbing call per byte.
+                    let mut run_start = None;
+                    for i in 0..n {
+                        let offset = index + i as u64 + 1;
+                        if self.filters.apply(offset) {
+                            run_start.get_or_insert(i);
+                        } else if let Some(start) = run_start.take() {
+                            writer.write_all(&buf[start..i])?;
+                        }
+                    }
+                    if let Some(start) = run_start.take() {
+                        writer.write_all(&buf[start..n])?;
+                    }
+
+                    index += n as u64;
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::File;
+    use std::io::{self, BufReader};
+    use std::str::FromStr;
+
+    use crate::filter::Filter;
+
+    fn testdata() -> File {
+        File::open("src/testdata/input.txt").unwrap()
+    }
+
+    fn execute_row(filters: Vec<Filter>, expected: &str) -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let mut slicer = RowSlicer::new(BufReader::new(testdata()), FilterSet::new(filters));
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    fn execute_row_inverted(filters: Vec<Filter>, expected: &str) -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let mut slicer = RowSlicer::new(
+            BufReader::new(testdata()),
+            FilterSet::new(filters).invert(true),
+        );
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn rowslc_slice_exact_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("1")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_exact_multiple_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("1")?, Filter::from_str("3")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_range_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("1:3")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+vault        1.8.4         dc15db720d79   2 days ago      186MB
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_range_multiple_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("1:2")?, Filter::from_str("4:5")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+vault        1.8.4         dc15db720d79   2 days ago      186MB
+postgres     14.0-alpine   ae192c4d3ada   17 months ago   152MB
+traefik      2.5           72bfc37343a4   18 months ago   68.9MB";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_exact_and_range_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("1")?, Filter::from_str("3:4")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+postgres     14.0-alpine   ae192c4d3ada   17 months ago   152MB
+";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_range_start_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("3:")?];
+        let expected = "\
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+postgres     14.0-alpine   ae192c4d3ada   17 months ago   152MB
+traefik      2.5           72bfc37343a4   18 months ago   68.9MB";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_range_end_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str(":3")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+vault        1.8.4         dc15db720d79   2 days ago      186MB
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_range_full_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str(":")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+vault        1.8.4         dc15db720d79   2 days ago      186MB
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+postgres     14.0-alpine   ae192c4d3ada   17 months ago   152MB
+traefik      2.5           72bfc37343a4   18 months ago   68.9MB";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_exact_negative_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("-1")?];
+        let expected = "traefik      2.5           72bfc37343a4   18 months ago   68.9MB";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_range_negative_start_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("-3:")?];
+        let expected = "\
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+postgres     14.0-alpine   ae192c4d3ada   17 months ago   152MB
+traefik      2.5           72bfc37343a4   18 months ago   68.9MB";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_range_negative_end_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str(":-2")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+vault        1.8.4         dc15db720d79   2 days ago      186MB
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+postgres     14.0-alpine   ae192c4d3ada   17 months ago   152MB
+";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_range_negative_magnitude_too_large_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("-100:")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+vault        1.8.4         dc15db720d79   2 days ago      186MB
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+postgres     14.0-alpine   ae192c4d3ada   17 months ago   152MB
+traefik      2.5           72bfc37343a4   18 months ago   68.9MB";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_range_negative_end_magnitude_too_large_ok() -> Result<(), Box<dyn Error>> {
+        // Unlike an overly-negative start, which clamps up to line 1 (the whole stream), an
+        // overly-negative end has nothing left before it to match, same as an out-of-range
+        // negative `Exact`.
+        let filters = vec![Filter::from_str(":-100")?];
+        execute_row(filters, "")
+    }
+
+    #[test]
+    fn rowslc_slice_empty_input_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("-1")?];
+        let mut slicer = RowSlicer::new(BufReader::new(io::empty()), FilterSet::new(filters));
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, "");
+        Ok(())
+    }
+
+    #[test]
+    fn rowslc_slice_suffix_window_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("-3:")?];
+        let expected = "\
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+postgres     14.0-alpine   ae192c4d3ada   17 months ago   152MB
+traefik      2.5           72bfc37343a4   18 months ago   68.9MB";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_suffix_window_larger_than_input_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("-100:")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+vault        1.8.4         dc15db720d79   2 days ago      186MB
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+postgres     14.0-alpine   ae192c4d3ada   17 months ago   152MB
+traefik      2.5           72bfc37343a4   18 months ago   68.9MB";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_suffix_window_huge_n_ok() -> Result<(), Box<dyn Error>> {
+        // A pathologically large N must not try to pre-allocate a window of that size; memory
+        // use should track the actual number of lines seen, not the requested width.
+        let filters = vec![Filter::from_str("-2000000000:")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+vault        1.8.4         dc15db720d79   2 days ago      186MB
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+postgres     14.0-alpine   ae192c4d3ada   17 months ago   152MB
+traefik      2.5           72bfc37343a4   18 months ago   68.9MB";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_suffix_window_empty_input_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("-3:")?];
+        let mut slicer = RowSlicer::new(BufReader::new(io::empty()), FilterSet::new(filters));
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, "");
+        Ok(())
+    }
+
+    #[test]
+    fn rowslc_slice_range_step_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("1:4:2")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+redis        6.2-alpine    6960a2858b36   3 days ago      31.3MB
+";
+
+        execute_row(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_invert_range_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("2:4")?];
+        let expected = "\
+REPOSITORY   TAG           IMAGE ID       CREATED         SIZE
+traefik      2.5           72bfc37343a4   18 months ago   68.9MB";
+
+        execute_row_inverted(filters, expected)
+    }
+
+    #[test]
+    fn rowslc_slice_invert_empty_filters_ok() -> Result<(), Box<dyn Error>> {
+        execute_row_inverted(Vec::new(), "")
+    }
+
+    #[test]
+    fn colslc_slice_exact_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("1")?];
+        let mut slicer = ColSlicer::new(BufReader::new(testdata()), FilterSet::new(filters), None, None);
+
+        let expected = "\
+REPOSITORY
+vault
+redis
+postgres
+traefik
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_exact_multiple_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("1")?, Filter::from_str("3")?];
+        let mut slicer = ColSlicer::new(BufReader::new(testdata()), FilterSet::new(filters), None, None);
+
+        let expected = "\
+REPOSITORY IMAGE
+vault dc15db720d79
+redis 6960a2858b36
+postgres ae192c4d3ada
+traefik 72bfc37343a4
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_range_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("1:3")?];
+        let mut slicer = ColSlicer::new(BufReader::new(testdata()), FilterSet::new(filters), None, None);
+
+        let expected = "\
+REPOSITORY TAG IMAGE
+vault 1.8.4 dc15db720d79
+redis 6.2-alpine 6960a2858b36
+postgres 14.0-alpine ae192c4d3ada
+traefik 2.5 72bfc37343a4
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_range_multiple_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("1:2")?, Filter::from_str("4:5")?];
+        let mut slicer = ColSlicer::new(BufReader::new(testdata()), FilterSet::new(filters), None, None);
+
+        let expected = "\
+REPOSITORY TAG ID CREATED
+vault 1.8.4 2 days
+redis 6.2-alpine 3 days
+postgres 14.0-alpine 17 months
+traefik 2.5 18 months
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_exact_and_range_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("1")?, Filter::from_str("3:4")?];
+        let mut slicer = ColSlicer::new(BufReader::new(testdata()), FilterSet::new(filters), None, None);
+
+        let expected = "\
+REPOSITORY IMAGE ID
+vault dc15db720d79 2
+redis 6960a2858b36 3
+postgres ae192c4d3ada 17
+traefik 72bfc37343a4 18
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_range_start_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("3:")?];
+        let mut slicer = ColSlicer::new(BufReader::new(testdata()), FilterSet::new(filters), None, None);
+
+        let expected = "\
+IMAGE ID CREATED SIZE
+dc15db720d79 2 days ago 186MB
+6960a2858b36 3 days ago 31.3MB
+ae192c4d3ada 17 months ago 152MB
+72bfc37343a4 18 months ago 68.9MB
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_range_end_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str(":3")?];
+        let mut slicer = ColSlicer::new(BufReader::new(testdata()), FilterSet::new(filters), None, None);
+
+        let expected = "\
+REPOSITORY TAG IMAGE
+vault 1.8.4 dc15db720d79
+redis 6.2-alpine 6960a2858b36
+postgres 14.0-alpine ae192c4d3ada
+traefik 2.5 72bfc37343a4
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_range_full_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str(":")?];
+        let mut slicer = ColSlicer::new(BufReader::new(testdata()), FilterSet::new(filters), None, None);
+
+        let expected = "\
+REPOSITORY TAG IMAGE ID CREATED SIZE
+vault 1.8.4 dc15db720d79 2 days ago 186MB
+redis 6.2-alpine 6960a2858b36 3 days ago 31.3MB
+postgres 14.0-alpine ae192c4d3ada 17 months ago 152MB
+traefik 2.5 72bfc37343a4 18 months ago 68.9MB
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_exact_negative_ok() -> Result<(), Box<dyn Error>> {
+        // Negative bounds are resolved against each line's own field count, which can differ
+        // from line to line (the header here has 6 whitespace-separated fields, the data rows
+        // have 7), unlike `RowSlicer` where the line count is fixed for the whole stream.
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("-1")?];
+        let mut slicer = ColSlicer::new(BufReader::new(testdata()), FilterSet::new(filters), None, None);
+
+        let expected = "\
+SIZE
+186MB
+31.3MB
+152MB
+68.9MB
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_range_negative_start_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("-2:")?];
+        let mut slicer = ColSlicer::new(BufReader::new(testdata()), FilterSet::new(filters), None, None);
+
+        let expected = "\
+CREATED SIZE
+ago 186MB
+ago 31.3MB
+ago 152MB
+ago 68.9MB
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_range_step_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("2::3")?];
+        let mut slicer = ColSlicer::new(BufReader::new(testdata()), FilterSet::new(filters), None, None);
+
+        let expected = "\
+TAG CREATED
+1.8.4 days
+6.2-alpine days
+14.0-alpine months
+2.5 months
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_delimiter_comma_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("1:2")?];
+        let mut slicer = ColSlicer::new(
+            BufReader::new("a,b,c\n,d,\n".as_bytes()),
+            FilterSet::new(filters),
+            Some(",".to_string()),
+            None,
+        );
+
+        let expected = "\
+a,b
+,d
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_delimiter_tab_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str(":")?];
+        let mut slicer = ColSlicer::new(
+            BufReader::new("a\tb\t\n".as_bytes()),
+            FilterSet::new(filters),
+            Some("\t".to_string()),
+            None,
+        );
+
+        let expected = "a\tb\t\n";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_delimiter_output_delimiter_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str(":")?];
+        let mut slicer = ColSlicer::new(
+            BufReader::new("a,b,c\n".as_bytes()),
+            FilterSet::new(filters),
+            Some(",".to_string()),
+            Some(";".to_string()),
+        );
+
+        let expected = "a;b;c\n";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_invert_exact_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let filters = vec![Filter::from_str("1")?];
+        let mut slicer = ColSlicer::new(
+            BufReader::new(testdata()),
+            FilterSet::new(filters).invert(true),
+            None,
+            None,
+        );
+
+        let expected = "\
+TAG IMAGE ID CREATED SIZE
+1.8.4 dc15db720d79 2 days ago 186MB
+6.2-alpine 6960a2858b36 3 days ago 31.3MB
+14.0-alpine ae192c4d3ada 17 months ago 152MB
+2.5 72bfc37343a4 18 months ago 68.9MB
+";
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn colslc_slice_invert_empty_filters_ok() -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let mut slicer = ColSlicer::new(
+            BufReader::new(testdata()),
+            FilterSet::new(Vec::new()).invert(true),
+            None,
+            None,
+        );
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(String::from_utf8(writer)?, "");
+        Ok(())
+    }
+
+    fn execute_bytes(input: &[u8], filters: Vec<Filter>, expected: &[u8]) -> Result<(), Box<dyn Error>> {
+        let mut writer = Vec::new();
+        let mut slicer = ByteSlicer::new(input, FilterSet::new(filters));
+
+        slicer.slice(&mut writer)?;
+        assert_eq!(writer, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn byteslc_slice_range_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("1:16")?];
+        execute_bytes(b"Hello, world! Goodbye, world!", filters, b"Hello, world! Go")
+    }
+
+    #[test]
+    fn byteslc_slice_range_start_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("8:")?];
+        execute_bytes(b"Hello, world!", filters, b"world!")
+    }
+
+    #[test]
+    fn byteslc_slice_range_past_eof_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("10:100")?];
+        execute_bytes(b"Hello, world!", filters, b"rld!")
+    }
+
+    #[test]
+    fn byteslc_slice_range_start_beyond_length_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("100:")?];
+        execute_bytes(b"Hello, world!", filters, b"")
+    }
+
+    #[test]
+    fn byteslc_slice_multiple_windows_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("1:5")?, Filter::from_str("8:12")?];
+        execute_bytes(b"Hello, world!", filters, b"Helloworld")
+    }
+
+    #[test]
+    fn byteslc_slice_empty_input_ok() -> Result<(), Box<dyn Error>> {
+        let filters = vec![Filter::from_str("1:16")?];
+        execute_bytes(b"", filters, b"")
+    }
+}